@@ -0,0 +1,34 @@
+use crate::math::{Isometry, Real};
+use crate::partitioning::SimdQuadTree;
+use crate::shape::{NormalConstraints, Shape};
+
+/// Trait implemented by shapes composed of multiple simpler shapes.
+///
+/// A composite shape is able to apply a closure to the shape of each of its parts, and exposes
+/// a [`SimdQuadTree`] for broad-phase traversal over those parts. This is implemented by
+/// [`crate::shape::Compound`].
+pub trait SimdCompositeShape {
+    /// Applies `f` to the sub-shape identified by `shape_id`.
+    ///
+    /// The `Option<&Isometry<Real>>` argument is the sub-shape's delta transform relative to
+    /// `self`'s local-space (or `None` if it has none). The `Option<&dyn NormalConstraints>`
+    /// argument carries the correction the narrow-phase should eventually apply to any contact
+    /// normal generated against this part — see [`NormalConstraints`] for why this matters at
+    /// internal edges/vertices, and for the current state of that wiring.
+    fn map_part_at(
+        &self,
+        shape_id: u32,
+        f: &mut dyn FnMut(Option<&Isometry<Real>>, &dyn Shape, Option<&dyn NormalConstraints>),
+    );
+
+    /// The acceleration structure used by this composite shape for broad-phase traversal of
+    /// its parts.
+    fn quadtree(&self) -> &SimdQuadTree<u32>;
+
+    /// The number of part ids of this composite shape: every valid `shape_id` for
+    /// `map_part_at` is below this value.
+    ///
+    /// For a [`crate::shape::Compound`] this also counts ids tombstoned by
+    /// `Compound::remove_shape`, so it can exceed the number of live parts.
+    fn nparts(&self) -> usize;
+}
@@ -3,9 +3,13 @@
 //!
 
 use crate::bounding_volume::{BoundingVolume, AABB};
-use crate::math::{Isometry, Real};
+use crate::mass_properties::MassProperties;
+use crate::math::{Isometry, Point, Real};
 use crate::partitioning::SimdQuadTree;
-use crate::shape::{Shape, SimdCompositeShape};
+use crate::query::contact::contact;
+use crate::query::Contact;
+use crate::shape::{ConvexPolygon, NormalConstraints, Shape, SimdCompositeShape};
+use crate::transformation::vhacd::{VHACDParameters, VHACD};
 use std::sync::Arc;
 
 /// A compound shape with an aabb bounding volume.
@@ -13,10 +17,16 @@ use std::sync::Arc;
 /// A compound shape is a shape composed of the union of several simpler shape. This is
 /// the main way of creating a concave shape from convex parts. Each parts can have its own
 /// delta transformation to shift or rotate it with regard to the other shapes.
+///
+/// Shapes can be added, removed, and replaced after construction (see [`Compound::add_shape`],
+/// [`Compound::remove_shape`], [`Compound::replace_shape`]). Removed slots are tombstoned
+/// rather than shifted out of `shapes`, so a shape's id (its index) stays valid for as long as
+/// the shape itself is alive, even if other shapes are later added or removed.
 pub struct Compound {
-    shapes: Vec<(Isometry<Real>, Arc<dyn Shape>)>,
+    shapes: Vec<Option<(Isometry<Real>, Arc<dyn Shape>)>>,
+    free_list: Vec<u32>,
     quadtree: SimdQuadTree<u32>,
-    aabbs: Vec<AABB>,
+    aabbs: Vec<Option<AABB>>,
     aabb: AABB,
 }
 
@@ -30,6 +40,117 @@ impl Compound {
             !shapes.is_empty(),
             "A compound shape must contain at least one shape."
         );
+
+        for &(_, ref shape) in &shapes {
+            if shape.as_composite_shape().is_some() {
+                panic!(
+                    "Nested composite shapes are not allowed by `Compound::new`; \
+                     use `Compound::new_nested` instead."
+                );
+            }
+        }
+
+        Self::from_flat_shapes(shapes)
+    }
+
+    /// Builds a new compound shape, recursively flattening any nested `Compound` found
+    /// amongst the inputs.
+    ///
+    /// Unlike [`Compound::new`], a sub-shape that is itself a `Compound` is not rejected:
+    /// its leaf shapes are spliced directly into the result, with their delta transforms
+    /// composed with the delta of the nested compound. This lets a compound be assembled
+    /// out of sub-compounds (e.g. a character built from limb sub-assemblies) while still
+    /// producing a single flat quadtree over the expanded leaf set.
+    ///
+    /// Panics if the input vector is empty, or if some of the provided shapes are composite
+    /// shapes other than `Compound` (e.g. a `TriMesh`, which cannot be flattened).
+    pub fn new_nested(shapes: Vec<(Isometry<Real>, Arc<dyn Shape>)>) -> Compound {
+        assert!(
+            !shapes.is_empty(),
+            "A compound shape must contain at least one shape."
+        );
+
+        let mut flat_shapes = Vec::new();
+
+        for (delta, shape) in shapes {
+            Self::flatten_into(&delta, &shape, &mut flat_shapes);
+        }
+
+        assert!(
+            !flat_shapes.is_empty(),
+            "A compound shape must contain at least one shape; every nested `Compound` given \
+             to `Compound::new_nested` had all of its sub-shapes removed."
+        );
+
+        Self::from_flat_shapes(flat_shapes)
+    }
+
+    /// Computes an approximate convex decomposition of the triangle mesh described by
+    /// `vertices`/`indices`, and builds a `Compound` out of the resulting convex parts.
+    ///
+    /// This runs the VHACD algorithm (voxelize the mesh, then recursively split clusters along
+    /// the axis-aligned plane that best reduces concavity, stopping once `params`' concavity
+    /// and hull-count budgets are met) and takes the convex hull of each resulting cluster as a
+    /// `ConvexPolygon` part with an identity delta. Use this when you only have a raw
+    /// concave mesh and want a ready-to-query compound; if you already have convex parts,
+    /// build the `Compound` directly with [`Compound::new`] instead.
+    ///
+    /// A cluster whose points are degenerate (e.g. collinear) can't form a `ConvexPolygon` and
+    /// is dropped from the result; this is only ever a handful of slivers out of many clusters
+    /// in practice. Panics if *every* cluster produced by VHACD turns out degenerate, since
+    /// that would otherwise silently hand back an empty `Compound`.
+    pub fn decompose(
+        vertices: &[Point<Real>],
+        indices: &[[u32; 3]],
+        params: &VHACDParameters,
+    ) -> Compound {
+        let vhacd = VHACD::decompose(params, vertices, indices, true);
+        let hulls = vhacd.compute_exact_convex_hulls(vertices, indices);
+        let num_hulls = hulls.len();
+
+        let shapes: Vec<_> = hulls
+            .into_iter()
+            .filter_map(|(points, _hull_indices)| ConvexPolygon::from_convex_hull(&points))
+            .map(|hull| (Isometry::identity(), Arc::new(hull) as Arc<dyn Shape>))
+            .collect();
+
+        assert!(
+            !shapes.is_empty(),
+            "Compound::decompose: VHACD produced {} cluster(s), but every one of them was \
+             degenerate and none yielded a usable convex hull.",
+            num_hulls
+        );
+
+        Compound::new(shapes)
+    }
+
+    // Recursively splices `shape` (or, if it's a `Compound`, every one of its leaves in turn)
+    // into `out`, composing deltas as needed. Panics on composite shapes that aren't `Compound`.
+    fn flatten_into(
+        delta: &Isometry<Real>,
+        shape: &Arc<dyn Shape>,
+        out: &mut Vec<(Isometry<Real>, Arc<dyn Shape>)>,
+    ) {
+        if let Some(compound) = shape.as_compound() {
+            for sub_shape in compound.shapes() {
+                if let Some((sub_delta, sub_shape)) = sub_shape {
+                    // Recurse instead of splicing directly: `sub_shape` may itself be a
+                    // `Compound` several levels deep (e.g. if it was built through
+                    // `Compound::new_nested` before being nested again here), and every level
+                    // must be flattened for the single-level-tree invariant to hold.
+                    Self::flatten_into(&(*delta * *sub_delta), sub_shape, out);
+                }
+            }
+        } else if shape.as_composite_shape().is_some() {
+            panic!("Only nested `Compound` shapes can be flattened by `Compound::new_nested`.");
+        } else {
+            out.push((*delta, shape.clone()));
+        }
+    }
+
+    // Shared tail of `new`/`new_nested`: builds the AABBs and quadtree from an already-flat
+    // (non-composite) set of sub-shapes.
+    fn from_flat_shapes(shapes: Vec<(Isometry<Real>, Arc<dyn Shape>)>) -> Compound {
         let mut aabbs = Vec::new();
         let mut leaves = Vec::new();
         let mut aabb = AABB::new_invalid();
@@ -38,12 +159,8 @@ impl Compound {
             let bv = shape.compute_aabb(delta);
 
             aabb.merge(&bv);
-            aabbs.push(bv.clone());
+            aabbs.push(Some(bv.clone()));
             leaves.push((i as u32, bv));
-
-            if shape.as_composite_shape().is_some() {
-                panic!("Nested composite shapes are not allowed.");
-            }
         }
 
         let mut quadtree = SimdQuadTree::new();
@@ -52,7 +169,8 @@ impl Compound {
         quadtree.clear_and_rebuild(leaves.into_iter(), 0.0);
 
         Compound {
-            shapes,
+            shapes: shapes.into_iter().map(Some).collect(),
+            free_list: Vec::new(),
             quadtree,
             aabbs,
             aabb,
@@ -62,8 +180,12 @@ impl Compound {
 
 impl Compound {
     /// The shapes of this compound shape.
+    ///
+    /// A `None` entry is a tombstone left behind by [`Compound::remove_shape`]: the id is
+    /// still reserved (it may be reused by a later [`Compound::add_shape`]) but no shape
+    /// currently occupies it.
     #[inline]
-    pub fn shapes(&self) -> &[(Isometry<Real>, Arc<dyn Shape>)] {
+    pub fn shapes(&self) -> &[Option<(Isometry<Real>, Arc<dyn Shape>)>] {
         &self.shapes[..]
     }
 
@@ -75,9 +197,202 @@ impl Compound {
 
     /// The shapes AABBs.
     #[inline]
-    pub fn aabbs(&self) -> &[AABB] {
+    pub fn aabbs(&self) -> &[Option<AABB>] {
         &self.aabbs[..]
     }
+
+    /// The mass, center of mass, and angular inertia of this compound, assuming each of its
+    /// parts has the given uniform `density`.
+    ///
+    /// Each part's own mass properties are transformed by that part's delta (composing its
+    /// center of mass and re-expressing its inertia tensor in the compound's local frame via
+    /// the parallel-axis theorem) before being summed, so the result correctly accounts for
+    /// every part's position and orientation relative to the others.
+    pub fn mass_properties(&self, density: Real) -> MassProperties {
+        self.shapes
+            .iter()
+            .filter_map(|shape| shape.as_ref())
+            .map(|(delta, shape)| shape.mass_properties(density).transform_by(delta))
+            .sum()
+    }
+
+    /// Collects, into `out`, the id of every part whose AABB overlaps `local_aabb` (expressed
+    /// in this compound's local-space).
+    ///
+    /// This scans `self.aabbs` linearly rather than querying `self.quadtree`: a leaf-level
+    /// `intersect_aabb`-style query method isn't confirmed to exist on this crate's vintage of
+    /// `SimdQuadTree` (see the `rebuild_quadtree` comment), so this sticks to the one
+    /// `BoundingVolume::intersects` check every `AABB` is already known to support rather than
+    /// guessing at an unconfirmed quadtree API. Revisit once that API is confirmed, to avoid the
+    /// linear scan for compounds with many parts.
+    pub fn intersecting_parts(&self, local_aabb: &AABB, out: &mut Vec<u32>) {
+        for (i, bv) in self.aabbs.iter().enumerate() {
+            if let Some(bv) = bv {
+                if bv.intersects(local_aabb) {
+                    out.push(i as u32);
+                }
+            }
+        }
+    }
+
+    /// Computes the deepest contact between this compound and `other`, positioned at `pos12`
+    /// relative to this compound (i.e. `pos12` maps `other`'s local-space into this compound's
+    /// local-space).
+    ///
+    /// Candidate parts are narrowed down with [`Compound::intersecting_parts`] against
+    /// `other`'s AABB loosened by `prediction`, so only parts close enough to `other` are ever
+    /// handed to the narrow-phase. Returns `None` if no part is within `prediction` of `other`.
+    pub fn contact_composite_shape_shape(
+        &self,
+        pos12: &Isometry<Real>,
+        other: &dyn Shape,
+        prediction: Real,
+    ) -> Option<Contact> {
+        let other_aabb = other.compute_aabb(pos12).loosened(prediction);
+
+        let mut candidates = Vec::new();
+        self.intersecting_parts(&other_aabb, &mut candidates);
+
+        let mut best_contact: Option<Contact> = None;
+
+        for part_id in candidates {
+            // The normal-constraints object is discarded here: `Compound` parts never supply
+            // one (see `NormalConstraints`'s doc comment), and no narrow-phase routine in this
+            // crate consumes one yet, so there's nothing to apply it to at this call site.
+            self.map_part_at(part_id, &mut |part_pos, part_shape, _normal_constraints| {
+                let part_pos12 = part_pos
+                    .map(|delta| delta.inverse() * *pos12)
+                    .unwrap_or(*pos12);
+
+                if let Ok(Some(contact)) = contact(&part_pos12, part_shape, other, prediction) {
+                    let contact = part_pos
+                        .map(|delta| contact.transform_by1(delta))
+                        .unwrap_or(contact);
+
+                    if best_contact
+                        .as_ref()
+                        .map_or(true, |best| contact.dist < best.dist)
+                    {
+                        best_contact = Some(contact);
+                    }
+                }
+            });
+        }
+
+        best_contact
+    }
+
+    // Rebuilds `self.quadtree` and `self.aabb` from scratch from the currently-live entries of
+    // `self.aabbs`.
+    //
+    // `SimdQuadTree` (this crate's vintage of it, at least) is only known to expose
+    // `clear_and_rebuild`; it's not confirmed to support single-leaf `insert`/`remove`, so
+    // add_shape/remove_shape/replace_shape all go through this full rebuild rather than trying
+    // to patch just the affected leaf. This is O(n) per mutation instead of the O(log n) the
+    // single-leaf approach would give, but it only relies on the one `SimdQuadTree` method this
+    // file already used before this series. It also doubles as the AABB recompute: merging
+    // only the new part's bv (as `add_shape`/`replace_shape` used to) can grow `self.aabb` but
+    // never shrink it, so a full merge is needed anywhere a part's AABB may shrink or disappear.
+    fn rebuild_quadtree(&mut self) {
+        let mut aabb = AABB::new_invalid();
+        let mut leaves = Vec::new();
+
+        for (i, bv) in self.aabbs.iter().enumerate() {
+            if let Some(bv) = bv {
+                aabb.merge(bv);
+                leaves.push((i as u32, bv.clone()));
+            }
+        }
+
+        self.quadtree.clear_and_rebuild(leaves.into_iter(), 0.0);
+        self.aabb = aabb;
+    }
+
+    /// Adds a new sub-shape to this compound, returning the id it was assigned.
+    ///
+    /// The returned id is stable: it won't change if other shapes are later added to or
+    /// removed from this compound. Ids freed by [`Compound::remove_shape`] are recycled
+    /// before new ones are allocated.
+    ///
+    /// Panics if `shape` is itself a composite shape: nested compounds can only be introduced
+    /// at construction time, through [`Compound::new_nested`], because flattening one here
+    /// would need to hand back more than one id from a function that returns a single `u32`.
+    pub fn add_shape(&mut self, delta: Isometry<Real>, shape: Arc<dyn Shape>) -> u32 {
+        assert!(
+            shape.as_composite_shape().is_none(),
+            "Nested composite shapes are not allowed; build them into this compound up-front \
+             with `Compound::new_nested` instead."
+        );
+
+        let bv = shape.compute_aabb(&delta);
+
+        let id = if let Some(id) = self.free_list.pop() {
+            self.shapes[id as usize] = Some((delta, shape));
+            self.aabbs[id as usize] = Some(bv);
+            id
+        } else {
+            let id = self.shapes.len() as u32;
+            self.shapes.push(Some((delta, shape)));
+            self.aabbs.push(Some(bv));
+            id
+        };
+
+        self.rebuild_quadtree();
+        id
+    }
+
+    /// Removes the sub-shape with the given id from this compound.
+    ///
+    /// The id is tombstoned (not reused until a future [`Compound::add_shape`] call) so any
+    /// other id already handed out remains valid.
+    ///
+    /// Panics if `id` refers to the only remaining live shape: a compound must always contain
+    /// at least one shape, the same invariant [`Compound::new`]/[`Compound::new_nested`]
+    /// enforce at construction.
+    pub fn remove_shape(&mut self, id: u32) {
+        if let Some(slot) = self.shapes.get_mut(id as usize) {
+            if slot.is_some() {
+                let live_count = self.shapes.iter().filter(|s| s.is_some()).count();
+                assert!(
+                    live_count > 1,
+                    "Compound::remove_shape: cannot remove the last live shape; a compound \
+                     must contain at least one shape."
+                );
+
+                self.shapes[id as usize] = None;
+                self.aabbs[id as usize] = None;
+                self.free_list.push(id);
+                self.rebuild_quadtree();
+            }
+        }
+    }
+
+    /// Replaces the sub-shape with the given id by a new delta/shape pair.
+    ///
+    /// This is equivalent to removing and re-adding a shape at the same id, except that the
+    /// id is preserved exactly (no tombstone is created, and no other id is ever recycled in
+    /// its place).
+    ///
+    /// Panics if `id` doesn't currently refer to a live shape (i.e. it's unused, or has been
+    /// tombstoned by [`Compound::remove_shape`] and not re-added since), or if `shape` is
+    /// itself a composite shape (see [`Compound::add_shape`] for why).
+    pub fn replace_shape(&mut self, id: u32, delta: Isometry<Real>, shape: Arc<dyn Shape>) {
+        assert!(
+            shape.as_composite_shape().is_none(),
+            "Nested composite shapes are not allowed; build them into this compound up-front \
+             with `Compound::new_nested` instead."
+        );
+        assert!(
+            self.shapes.get(id as usize).map_or(false, Option::is_some),
+            "Compound::replace_shape: `id` doesn't refer to a live shape."
+        );
+
+        let bv = shape.compute_aabb(&delta);
+
+        self.shapes[id as usize] = Some((delta, shape));
+        self.aabbs[id as usize] = Some(bv);
+        self.rebuild_quadtree();
+    }
 }
 
 impl SimdCompositeShape for Compound {
@@ -87,9 +402,16 @@ impl SimdCompositeShape for Compound {
     }
 
     #[inline]
-    fn map_part_at(&self, shape_id: u32, f: &mut dyn FnMut(Option<&Isometry<Real>>, &dyn Shape)) {
-        if let Some(shape) = self.shapes.get(shape_id as usize) {
-            f(Some(&shape.0), &*shape.1)
+    fn map_part_at(
+        &self,
+        shape_id: u32,
+        f: &mut dyn FnMut(Option<&Isometry<Real>>, &dyn Shape, Option<&dyn NormalConstraints>),
+    ) {
+        if let Some(Some(shape)) = self.shapes.get(shape_id as usize) {
+            // A `Compound`'s parts are never composite shapes (they are rejected or flattened
+            // when the compound is built or edited), so no part has internal edges to protect
+            // and there's no normal constraint to report.
+            f(Some(&shape.0), &*shape.1, None)
         }
     }
 
@@ -97,4 +419,176 @@ impl SimdCompositeShape for Compound {
     fn quadtree(&self) -> &SimdQuadTree<u32> {
         &self.quadtree
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shape::Ball;
+    use approx::assert_relative_eq;
+
+    fn ball_at(x: Real, y: Real, radius: Real) -> (Isometry<Real>, Arc<dyn Shape>) {
+        (
+            Isometry::translation(x, y),
+            Arc::new(Ball::new(radius)) as Arc<dyn Shape>,
+        )
+    }
+
+    #[test]
+    fn add_remove_replace_preserve_ids() {
+        let mut compound = Compound::new(vec![ball_at(0.0, 0.0, 1.0), ball_at(2.0, 0.0, 1.0)]);
+
+        let id_a = compound.add_shape(Isometry::translation(4.0, 0.0), Arc::new(Ball::new(1.0)));
+        assert_eq!(compound.shapes().len(), 3);
+
+        // Removing a shape tombstones its id rather than shifting everything else down.
+        compound.remove_shape(0);
+        assert!(compound.shapes()[0].is_none());
+        assert!(compound.shapes()[1].is_some());
+        assert_eq!(id_a, 2);
+
+        // A later add_shape recycles the freed id instead of growing the vector.
+        let id_b = compound.add_shape(Isometry::translation(6.0, 0.0), Arc::new(Ball::new(1.0)));
+        assert_eq!(id_b, 0);
+        assert_eq!(compound.shapes().len(), 3);
+
+        // replace_shape keeps the id fixed and doesn't disturb the free list.
+        compound.replace_shape(1, Isometry::translation(2.0, 1.0), Arc::new(Ball::new(2.0)));
+        assert_eq!(compound.shapes()[1].as_ref().unwrap().0.translation.y, 1.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn replace_shape_rejects_tombstoned_id() {
+        let mut compound = Compound::new(vec![ball_at(0.0, 0.0, 1.0), ball_at(2.0, 0.0, 1.0)]);
+        compound.remove_shape(0);
+        // id 0 is tombstoned, not live: replacing it must panic rather than resurrect it
+        // behind add_shape's back.
+        compound.replace_shape(0, Isometry::identity(), Arc::new(Ball::new(1.0)));
+    }
+
+    #[test]
+    fn removing_a_shape_shrinks_the_merged_aabb() {
+        let mut compound = Compound::new(vec![ball_at(0.0, 0.0, 1.0), ball_at(10.0, 0.0, 1.0)]);
+        let full_extent = compound.aabb().maxs.x - compound.aabb().mins.x;
+
+        compound.remove_shape(1);
+        let shrunk_extent = compound.aabb().maxs.x - compound.aabb().mins.x;
+
+        assert!(shrunk_extent < full_extent);
+    }
+
+    #[test]
+    #[should_panic]
+    fn remove_shape_panics_on_last_live_shape() {
+        let mut compound = Compound::new(vec![ball_at(0.0, 0.0, 1.0), ball_at(2.0, 0.0, 1.0)]);
+        compound.remove_shape(0);
+        // Only id 1 is live at this point: removing it too would leave the compound with no
+        // shapes at all, which must panic rather than silently produce an empty compound.
+        compound.remove_shape(1);
+    }
+
+    #[test]
+    fn new_nested_flattens_and_composes_deltas() {
+        let inner = Compound::new(vec![ball_at(1.0, 0.0, 1.0), ball_at(-1.0, 0.0, 1.0)]);
+        let outer = Compound::new_nested(vec![(
+            Isometry::translation(5.0, 0.0),
+            Arc::new(inner) as Arc<dyn Shape>,
+        )]);
+
+        // The nested compound's two leaves should be spliced in directly, with the outer
+        // delta composed onto each inner delta.
+        assert_eq!(outer.shapes().len(), 2);
+        let xs: Vec<Real> = outer
+            .shapes()
+            .iter()
+            .map(|s| s.as_ref().unwrap().0.translation.x)
+            .collect();
+        assert!(xs.contains(&6.0));
+        assert!(xs.contains(&4.0));
+    }
+
+    #[test]
+    fn new_nested_flattens_three_levels_deep() {
+        let innermost = Compound::new(vec![ball_at(1.0, 0.0, 1.0)]);
+        let middle = Compound::new_nested(vec![(
+            Isometry::translation(10.0, 0.0),
+            Arc::new(innermost) as Arc<dyn Shape>,
+        )]);
+        let outer = Compound::new_nested(vec![(
+            Isometry::translation(100.0, 0.0),
+            Arc::new(middle) as Arc<dyn Shape>,
+        )]);
+
+        // Flattening must recurse all the way down: the single leaf should end up with every
+        // level's delta composed onto it, not a `Compound` still sitting un-flattened in
+        // `outer`'s own leaf set.
+        assert_eq!(outer.shapes().len(), 1);
+        let (leaf_delta, leaf_shape) = outer.shapes()[0].as_ref().unwrap();
+        assert!(leaf_shape.as_composite_shape().is_none());
+        assert_relative_eq!(leaf_delta.translation.x, 111.0, epsilon = 1.0e-6);
+    }
+
+    #[test]
+    fn mass_properties_sums_translated_parts() {
+        let compound = Compound::new(vec![ball_at(-1.0, 0.0, 1.0), ball_at(1.0, 0.0, 1.0)]);
+        let mp = compound.mass_properties(1.0);
+        let single = Ball::new(1.0).mass_properties(1.0);
+
+        // Two identical balls placed symmetrically about the origin: total mass doubles, and
+        // by symmetry the combined center of mass sits exactly at the origin.
+        assert_relative_eq!(mp.mass(), single.mass() * 2.0, epsilon = 1.0e-6);
+        assert_relative_eq!(mp.local_com.x, 0.0, epsilon = 1.0e-6);
+    }
+
+    #[test]
+    fn decompose_builds_a_queryable_compound() {
+        // An "L" shape made of three axis-aligned unit squares, concave enough that
+        // VHACD should split it into (at least) two convex clusters.
+        let vertices = vec![
+            Point::new(0.0, 0.0),
+            Point::new(2.0, 0.0),
+            Point::new(2.0, 1.0),
+            Point::new(1.0, 1.0),
+            Point::new(1.0, 2.0),
+            Point::new(0.0, 2.0),
+        ];
+        let indices = [[0u32, 1, 2], [0, 2, 3], [0, 3, 4], [0, 4, 5]];
+
+        let compound = Compound::decompose(&vertices, &indices, &VHACDParameters::default());
+
+        assert!(!compound.shapes().is_empty());
+
+        // The result should be a normal, queryable Compound: a point inside the "L" should hit
+        // at least one part.
+        let mut hits = Vec::new();
+        let probe = AABB::new(Point::new(0.4, 0.4), Point::new(0.6, 0.6));
+        compound.intersecting_parts(&probe, &mut hits);
+        assert!(!hits.is_empty());
+    }
+
+    #[test]
+    fn intersecting_parts_finds_only_overlapping_parts() {
+        let compound = Compound::new(vec![ball_at(0.0, 0.0, 1.0), ball_at(20.0, 0.0, 1.0)]);
+
+        let mut hits = Vec::new();
+        let query_aabb = AABB::new(Point::new(-1.5, -1.5), Point::new(1.5, 1.5));
+        compound.intersecting_parts(&query_aabb, &mut hits);
+
+        assert_eq!(hits, vec![0]);
+    }
+
+    #[test]
+    fn contact_composite_shape_shape_reports_deepest_part() {
+        let compound = Compound::new(vec![ball_at(0.0, 0.0, 1.0), ball_at(20.0, 0.0, 1.0)]);
+        let other = Ball::new(1.0);
+
+        // `other` sits inside the first ball and far from the second: the contact should come
+        // back from part 0, with a negative (penetrating) distance.
+        let pos12 = Isometry::translation(0.5, 0.0);
+        let contact = compound
+            .contact_composite_shape_shape(&pos12, &other, 0.1)
+            .expect("expected a contact with the overlapping first part");
+        assert!(contact.dist < 0.0);
+    }
+}
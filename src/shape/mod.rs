@@ -0,0 +1,9 @@
+//! Shape types and traits used for geometric queries.
+
+pub use self::composite_shape::SimdCompositeShape;
+pub use self::compound::Compound;
+pub use self::normal_constraints::NormalConstraints;
+
+mod composite_shape;
+mod compound;
+mod normal_constraints;
@@ -0,0 +1,32 @@
+use crate::math::{Real, Vector};
+use crate::shape::FeatureId;
+use na::Unit;
+
+/// Constraints applied to contact normals generated against a specific part of a composite
+/// shape.
+///
+/// Some composite shapes (e.g. a `TriMesh`) expose "internal" edges and vertices shared between
+/// adjacent convex parts. A naively-computed contact normal near such a boundary can point
+/// slightly into a neighboring part's half-space, producing the classic "ghost bump" artifact
+/// when sliding across the boundary. A `NormalConstraints` implementation fixes this up by
+/// projecting the normal onto the cone of directions that are admissible for the visited part.
+///
+/// This trait is currently inert scaffolding: [`SimdCompositeShape::map_part_at`] threads an
+/// `Option<&dyn NormalConstraints>` through to callers (`Compound` always reports `None`, since
+/// a `Compound`'s own parts have no internal-edge structure), but nothing in this crate yet
+/// calls [`NormalConstraints::project_local_normal`] to apply the correction — that lands
+/// alongside whichever shape first has internal edges to protect (e.g. a future `TriMesh`
+/// per-triangle implementation), and the narrow-phase call site that consumes it.
+///
+/// [`SimdCompositeShape::map_part_at`]: crate::shape::SimdCompositeShape::map_part_at
+pub trait NormalConstraints {
+    /// Projects `normal` (a contact normal generated against `feature` of the part this
+    /// constraint object was obtained from) into this part's cone of admissible directions.
+    ///
+    /// Normals that already lie inside the cone are returned unchanged.
+    fn project_local_normal(
+        &self,
+        feature: FeatureId,
+        normal: Unit<Vector<Real>>,
+    ) -> Unit<Vector<Real>>;
+}